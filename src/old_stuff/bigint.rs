@@ -0,0 +1,582 @@
+// Copyright 2013-2014 The Num-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Arbitrary-precision integers, `BigUint` and `BigInt`.
+//!
+//! `BigUint` stores an unsigned magnitude as a little-endian vector of
+//! 32-bit "big digits". `BigInt` pairs a `BigUint` magnitude with a
+//! `Sign`, and is the type that plugs into the rest of the crate by
+//! implementing `CommutativeRing` and `Integer`.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use algebra::CommutativeRing;
+use old_stuff::num::Integer;
+
+/// A single machine-word digit of a `BigUint`, stored in base `2^32`.
+pub type BigDigit = u32;
+
+/// A double-width digit, wide enough to hold the result of multiplying
+/// two `BigDigit`s together along with a carry.
+pub type DoubleBigDigit = u64;
+
+const BITS_PER_DIGIT: usize = 32;
+
+/// Strip any trailing (most significant) zero digits, so that two
+/// `BigUint`s with the same value always compare and hash equal.
+fn normalize(mut data: Vec<BigDigit>) -> Vec<BigDigit> {
+    while data.last() == Some(&0) {
+        data.pop();
+    }
+    data
+}
+
+/// An arbitrary-precision unsigned integer, represented as a
+/// little-endian vector of `BigDigit`s (`data[0]` is the least
+/// significant digit). The vector never has trailing zero digits, so
+/// that the empty vector is the unique representation of zero.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    data: Vec<BigDigit>,
+}
+
+impl BigUint {
+    /// Creates a `BigUint` from a little-endian vector of digits,
+    /// normalizing away any trailing zero digits.
+    pub fn new(data: Vec<BigDigit>) -> BigUint {
+        BigUint { data: normalize(data) }
+    }
+
+    /// The `BigUint` representing zero.
+    pub fn zero() -> BigUint {
+        BigUint { data: Vec::new() }
+    }
+
+    /// The `BigUint` representing one.
+    pub fn one() -> BigUint {
+        BigUint { data: vec![1] }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The number of bits needed to represent this value (`0` for zero).
+    fn bits(&self) -> usize {
+        match self.data.last() {
+            None => 0,
+            Some(&top) => {
+                (self.data.len() - 1) * BITS_PER_DIGIT
+                    + (BITS_PER_DIGIT - top.leading_zeros() as usize)
+            }
+        }
+    }
+
+    fn get_bit(&self, i: usize) -> bool {
+        let word = i / BITS_PER_DIGIT;
+        let bit = i % BITS_PER_DIGIT;
+        match self.data.get(word) {
+            None => false,
+            Some(&digit) => (digit >> bit) & 1 == 1,
+        }
+    }
+
+    fn shl1(&mut self) {
+        let mut carry = 0u32;
+        for digit in self.data.iter_mut() {
+            let new_carry = *digit >> 31;
+            *digit = (*digit << 1) | carry;
+            carry = new_carry;
+        }
+        if carry != 0 {
+            self.data.push(carry);
+        }
+    }
+
+    fn set_bit(&mut self, i: usize) {
+        let word = i / BITS_PER_DIGIT;
+        let bit = i % BITS_PER_DIGIT;
+        while self.data.len() <= word {
+            self.data.push(0);
+        }
+        self.data[word] |= 1 << bit;
+    }
+
+    /// Schoolbook long division, returning `(quotient, remainder)`.
+    ///
+    /// Implemented as a binary shift-and-subtract so that it only needs
+    /// comparison, subtraction and bit-shifting of `BigUint`s.
+    pub fn div_rem(&self, other: &BigUint) -> (BigUint, BigUint) {
+        assert!(!other.is_zero(), "division by zero");
+        if *self < *other {
+            return (BigUint::zero(), self.clone());
+        }
+
+        let mut quotient = BigUint::zero();
+        let mut remainder = BigUint::zero();
+        for i in (0..self.bits()).rev() {
+            remainder.shl1();
+            if self.get_bit(i) {
+                remainder.set_bit(0);
+            }
+            if remainder >= *other {
+                remainder = &remainder - other;
+                quotient.set_bit(i);
+            }
+        }
+        (BigUint::new(quotient.data), BigUint::new(remainder.data))
+    }
+
+    /// Formats this value in the given radix (`2..=16`).
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!(radix >= 2 && radix <= 16, "radix must be between 2 and 16");
+        if self.is_zero() {
+            return "0".to_string();
+        }
+
+        let base = BigUint::new(vec![radix]);
+        let mut digits = Vec::new();
+        let mut n = self.clone();
+        while !n.is_zero() {
+            let (q, r) = n.div_rem(&base);
+            let digit = if r.is_zero() { 0 } else { r.data[0] };
+            digits.push(std::char::from_digit(digit, radix).unwrap());
+            n = q;
+        }
+        digits.reverse();
+        digits.into_iter().collect()
+    }
+
+    /// Parses a `BigUint` from a string of digits in the given radix
+    /// (`2..=16`).
+    pub fn from_str_radix(s: &str, radix: u32) -> Option<BigUint> {
+        assert!(radix >= 2 && radix <= 16, "radix must be between 2 and 16");
+        if s.is_empty() {
+            return None;
+        }
+
+        let base = BigUint::new(vec![radix]);
+        let mut result = BigUint::zero();
+        for c in s.chars() {
+            let digit = match c.to_digit(radix) {
+                Some(d) => d,
+                None => return None,
+            };
+            result = &(&result * &base) + &BigUint::new(vec![digit]);
+        }
+        Some(result)
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        match self.data.len().cmp(&other.data.len()) {
+            Ordering::Equal => self.data.iter().rev().cmp(other.data.iter().rev()),
+            other_ordering => other_ordering,
+        }
+    }
+}
+
+impl<'a, 'b> Add<&'b BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &'b BigUint) -> BigUint {
+        let mut result = Vec::with_capacity(self.data.len().max(other.data.len()) + 1);
+        let mut carry: DoubleBigDigit = 0;
+        for i in 0..self.data.len().max(other.data.len()) {
+            let a = *self.data.get(i).unwrap_or(&0) as DoubleBigDigit;
+            let b = *other.data.get(i).unwrap_or(&0) as DoubleBigDigit;
+            let sum = a + b + carry;
+            result.push(sum as BigDigit);
+            carry = sum >> BITS_PER_DIGIT;
+        }
+        if carry != 0 {
+            result.push(carry as BigDigit);
+        }
+        BigUint::new(result)
+    }
+}
+
+impl<'a, 'b> Sub<&'b BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    /// Subtracts `other` from `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other > self`, since `BigUint` cannot represent a
+    /// negative value.
+    fn sub(self, other: &'b BigUint) -> BigUint {
+        assert!(*self >= *other, "BigUint subtraction would underflow");
+        let mut result = Vec::with_capacity(self.data.len());
+        let mut borrow: i64 = 0;
+        for i in 0..self.data.len() {
+            let a = *self.data.get(i).unwrap_or(&0) as i64;
+            let b = *other.data.get(i).unwrap_or(&0) as i64;
+            let mut diff = a - b - borrow;
+            if diff < 0 {
+                diff += 1 << BITS_PER_DIGIT;
+                borrow = 1;
+            } else {
+                borrow = 0;
+            }
+            result.push(diff as BigDigit);
+        }
+        BigUint::new(result)
+    }
+}
+
+impl<'a, 'b> Mul<&'b BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &'b BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut result = vec![0 as BigDigit; self.data.len() + other.data.len()];
+        for (i, &a) in self.data.iter().enumerate() {
+            let mut carry: DoubleBigDigit = 0;
+            for (j, &b) in other.data.iter().enumerate() {
+                let product = a as DoubleBigDigit * b as DoubleBigDigit
+                    + result[i + j] as DoubleBigDigit
+                    + carry;
+                result[i + j] = product as BigDigit;
+                carry = product >> BITS_PER_DIGIT;
+            }
+            if carry != 0 {
+                result[i + other.data.len()] += carry as BigDigit;
+            }
+        }
+        BigUint::new(result)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str_radix(10))
+    }
+}
+
+/// The sign of a `BigInt`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Sign {
+    Minus,
+    NoSign,
+    Plus,
+}
+
+impl Neg for Sign {
+    type Output = Sign;
+
+    fn neg(self) -> Sign {
+        match self {
+            Sign::Minus => Sign::Plus,
+            Sign::NoSign => Sign::NoSign,
+            Sign::Plus => Sign::Minus,
+        }
+    }
+}
+
+/// An arbitrary-precision signed integer, built from a `BigUint`
+/// magnitude and a `Sign`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    sign: Sign,
+    data: BigUint,
+}
+
+impl BigInt {
+    /// Creates a `BigInt` from a sign and magnitude, normalizing the sign
+    /// to `NoSign` if the magnitude is zero.
+    pub fn from_biguint(sign: Sign, data: BigUint) -> BigInt {
+        if data.is_zero() {
+            BigInt { sign: Sign::NoSign, data: data }
+        } else {
+            BigInt { sign: sign, data: data }
+        }
+    }
+
+    pub fn from_i64(n: i64) -> BigInt {
+        let sign = if n < 0 {
+            Sign::Minus
+        } else if n == 0 {
+            Sign::NoSign
+        } else {
+            Sign::Plus
+        };
+        let magnitude = n.unsigned_abs();
+        let data = BigUint::new(vec![magnitude as BigDigit, (magnitude >> 32) as BigDigit]);
+        BigInt::from_biguint(sign, data)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.data.is_zero()
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt::from_biguint(Sign::Plus, self.data.clone())
+    }
+
+    pub fn sign(&self) -> Sign {
+        self.sign
+    }
+
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        match self.sign {
+            Sign::Minus => format!("-{}", self.data.to_str_radix(radix)),
+            _ => self.data.to_str_radix(radix),
+        }
+    }
+
+    pub fn from_str_radix(s: &str, radix: u32) -> Option<BigInt> {
+        let (sign, digits) = if let Some(rest) = s.strip_prefix('-') {
+            (Sign::Minus, rest)
+        } else {
+            (Sign::Plus, s)
+        };
+        BigUint::from_str_radix(digits, radix).map(|data| BigInt::from_biguint(sign, data))
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.sign, other.sign) {
+            (Sign::Minus, Sign::Minus) => other.data.cmp(&self.data),
+            (Sign::Minus, _) => Ordering::Less,
+            (_, Sign::Minus) => Ordering::Greater,
+            (Sign::NoSign, Sign::NoSign) => Ordering::Equal,
+            (Sign::NoSign, Sign::Plus) => Ordering::Less,
+            (Sign::Plus, Sign::NoSign) => Ordering::Greater,
+            (Sign::Plus, Sign::Plus) => self.data.cmp(&other.data),
+        }
+    }
+}
+
+impl<'a, 'b> Add<&'b BigInt> for &'a BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &'b BigInt) -> BigInt {
+        match (self.sign, other.sign) {
+            (Sign::NoSign, _) => other.clone(),
+            (_, Sign::NoSign) => self.clone(),
+            (a, b) if a == b => BigInt::from_biguint(a, &self.data + &other.data),
+            _ => {
+                if self.data >= other.data {
+                    BigInt::from_biguint(self.sign, &self.data - &other.data)
+                } else {
+                    BigInt::from_biguint(other.sign, &other.data - &self.data)
+                }
+            }
+        }
+    }
+}
+
+impl<'a, 'b> Sub<&'b BigInt> for &'a BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &'b BigInt) -> BigInt {
+        self + &(-other.clone())
+    }
+}
+
+impl<'a, 'b> Mul<&'b BigInt> for &'a BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &'b BigInt) -> BigInt {
+        let data = &self.data * &other.data;
+        let sign = if data.is_zero() {
+            Sign::NoSign
+        } else if self.sign == other.sign {
+            Sign::Plus
+        } else {
+            Sign::Minus
+        };
+        BigInt::from_biguint(sign, data)
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_biguint(-self.sign, self.data)
+    }
+}
+
+impl Add for BigInt {
+    type Output = BigInt;
+    fn add(self, other: BigInt) -> BigInt {
+        &self + &other
+    }
+}
+
+impl Sub for BigInt {
+    type Output = BigInt;
+    fn sub(self, other: BigInt) -> BigInt {
+        &self - &other
+    }
+}
+
+impl Mul for BigInt {
+    type Output = BigInt;
+    fn mul(self, other: BigInt) -> BigInt {
+        &self * &other
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str_radix(10))
+    }
+}
+
+// `BigInt` derives `Clone` but is deliberately not `Copy` (it owns a
+// `Vec`), which is why `Integer`'s default methods are written in terms
+// of `.clone()` rather than an implicit dereference-copy.
+impl CommutativeRing for BigInt {}
+
+impl Integer for BigInt {
+    fn t_div(a: &BigInt, b: &BigInt) -> BigInt {
+        Integer::t_div_mod(a, b).0
+    }
+
+    fn t_mod(a: &BigInt, b: &BigInt) -> BigInt {
+        Integer::t_div_mod(a, b).1
+    }
+
+    fn t_div_mod(a: &BigInt, b: &BigInt) -> (BigInt, BigInt) {
+        let (q, r) = a.data.div_rem(&b.data);
+        let q_sign = if q.is_zero() {
+            Sign::NoSign
+        } else if a.sign == b.sign {
+            Sign::Plus
+        } else {
+            Sign::Minus
+        };
+        (BigInt::from_biguint(q_sign, q), BigInt::from_biguint(a.sign, r))
+    }
+
+    fn f_div(a: &BigInt, b: &BigInt) -> BigInt {
+        Integer::f_div_mod(a, b).0
+    }
+
+    fn f_mod(a: &BigInt, b: &BigInt) -> BigInt {
+        Integer::f_div_mod(a, b).1
+    }
+
+    fn f_div_mod(a: &BigInt, b: &BigInt) -> (BigInt, BigInt) {
+        let (q, r) = Integer::t_div_mod(a, b);
+        if !r.is_zero() && (a.sign == Sign::Minus) != (b.sign == Sign::Minus) {
+            (q - BigInt::from_i64(1), r + b.clone())
+        } else {
+            (q, r)
+        }
+    }
+
+    fn gcd(a: &BigInt, b: &BigInt) -> BigInt {
+        let mut a = a.abs();
+        let mut b = b.abs();
+        while !b.is_zero() {
+            let r = Integer::t_mod(&a, &b);
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    fn lcm(a: &BigInt, b: &BigInt) -> BigInt {
+        if a.is_zero() && b.is_zero() {
+            return BigInt::from_i64(0);
+        }
+        let g = Integer::gcd(a, b);
+        Integer::t_div(a, &g) * b.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BigInt, BigUint, Sign};
+    use old_stuff::num::Integer;
+
+    #[test]
+    fn test_biguint_add_sub_mul() {
+        let a = BigUint::new(vec![0xffffffff, 0xffffffff]);
+        let b = BigUint::new(vec![1]);
+        assert_eq!(&a + &b, BigUint::new(vec![0, 0, 1]));
+        assert_eq!(&(&a + &b) - &b, a);
+
+        let x = BigUint::new(vec![1_000_000]);
+        let y = BigUint::new(vec![1_000_000]);
+        assert_eq!(&x * &y, BigUint::new(vec![1_000_000_000_000u64 as u32,
+                                               (1_000_000_000_000u64 >> 32) as u32]));
+    }
+
+    #[test]
+    fn test_biguint_div_rem() {
+        let a = BigUint::from_str_radix("1000000000000000000", 10).unwrap();
+        let b = BigUint::new(vec![7]);
+        let (q, r) = a.div_rem(&b);
+        assert_eq!(&(&q * &b) + &r, a);
+        assert!(r < b);
+    }
+
+    #[test]
+    fn test_biguint_str_radix_roundtrip() {
+        let n = BigUint::from_str_radix("ff1234abcd", 16).unwrap();
+        assert_eq!(n.to_str_radix(16), "ff1234abcd");
+    }
+
+    #[test]
+    fn test_bigint_ordering_and_arithmetic() {
+        let a = BigInt::from_i64(-5);
+        let b = BigInt::from_i64(3);
+        assert!(a < b);
+        assert_eq!(a.clone() + b.clone(), BigInt::from_i64(-2));
+        assert_eq!(a.clone() * b.clone(), BigInt::from_i64(-15));
+        assert_eq!(a.sign(), Sign::Minus);
+    }
+
+    #[test]
+    fn test_bigint_integer_impl() {
+        let a = BigInt::from_i64(-8);
+        let b = BigInt::from_i64(3);
+        assert_eq!(Integer::t_div(&a, &b), BigInt::from_i64(-2));
+        assert_eq!(Integer::t_mod(&a, &b), BigInt::from_i64(-2));
+        assert_eq!(Integer::f_div(&a, &b), BigInt::from_i64(-3));
+        assert_eq!(Integer::f_mod(&a, &b), BigInt::from_i64(1));
+        assert_eq!(Integer::gcd(&BigInt::from_i64(12), &BigInt::from_i64(18)), BigInt::from_i64(6));
+    }
+
+    #[test]
+    fn test_bigint_lcm_zero_zero() {
+        // gcd(0, 0) == 0, so the naive `a / gcd(a, b) * b` would divide
+        // by zero; lcm(0, 0) must be special-cased to 0 instead.
+        let zero = BigInt::from_i64(0);
+        assert_eq!(Integer::lcm(&zero, &zero), zero);
+    }
+}
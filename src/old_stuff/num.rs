@@ -25,10 +25,10 @@ use ident;
 /// _[Division and Modulus for Computer Scientists]
 /// (http://legacy.cs.uu.nl/daan/download/papers/divmodnote-letter.pdf)_.
 pub trait Integer
-    : Eq + Ord
+    : Eq + Ord + Clone
     + CommutativeRing {
     #[inline]
-    fn succ(&self) -> Self { *self + ident::unit() }
+    fn succ(&self) -> Self { self.clone() + ident::unit() }
 
     /// Truncated division satisfying:
     ///
@@ -78,11 +78,69 @@ pub trait Integer
         (f_div(a, b), f_mod(a, b))
     }
 
+    /// Euclidean division satisfying:
+    ///
+    /// ~~~notrust
+    /// 0 <= e_mod(a, b) < |b|                  ∀ a, b ∈ Self where b ≠ 0
+    /// a = b * e_div(a, b) + e_mod(a, b)
+    /// ~~~
+    ///
+    /// This is the convention where the remainder is always non-negative,
+    /// regardless of the signs of `a` and `b`, as described in Leijen's
+    /// division note. The default implementation derives it from `t_div_mod`
+    /// by correcting the truncated quotient and remainder when the
+    /// remainder came out negative.
+    #[inline]
+    fn e_div(a: &Self, b: &Self) -> Self {
+        e_div_mod(a, b).0
+    }
+
+    /// The remainder after Euclidean division. See `e_div` for the defining
+    /// property.
+    #[inline]
+    fn e_mod(a: &Self, b: &Self) -> Self {
+        e_div_mod(a, b).1
+    }
+
+    /// Calculates `e_div` and `e_mod` simultaneously.
+    #[inline]
+    fn e_div_mod(a: &Self, b: &Self) -> (Self, Self) {
+        let (q, r) = t_div_mod(a, b);
+        if r < ident::zero() {
+            if *b > ident::zero() {
+                (q - ident::unit(), r + b.clone())
+            } else {
+                (q + ident::unit(), r - b.clone())
+            }
+        } else {
+            (q, r)
+        }
+    }
+
     /// Greatest Common Divisor (GCD)
     fn gcd(a: &Self, b: &Self) -> Self;
 
     /// Lowest Common Multiple (LCM)
     fn lcm(a: &Self, b: &Self) -> Self;
+
+    /// Returns `true` if `a` evenly divides `b`, i.e. `b % a == 0`.
+    #[inline]
+    fn divides(a: &Self, b: &Self) -> bool {
+        t_mod(b, a) == ident::zero()
+    }
+
+    /// Returns `true` if `self` is divisible by `2`.
+    #[inline]
+    fn is_even(&self) -> bool {
+        let two = ident::unit() + ident::unit();
+        t_mod(self, &two) == ident::zero()
+    }
+
+    /// Returns `true` if `self` is not divisible by `2`.
+    #[inline]
+    fn is_odd(&self) -> bool {
+        !self.is_even()
+    }
 }
 
 pub trait ModularInteger
@@ -91,7 +149,57 @@ pub trait ModularInteger
     fn max_value() -> Self;
     fn congruent(x: &Self, y: &Self) -> bool;
     #[inline]
-    fn pred(&self) -> Self { *self - ident::unit() }
+    fn pred(&self) -> Self { self.clone() - ident::unit() }
+
+    /// Extended Euclidean algorithm, returning `(g, x, y)` such that
+    /// `a*x + b*y = g = gcd(a, b)`.
+    fn extended_gcd(a: &Self, b: &Self) -> (Self, Self, Self) {
+        let (mut old_r, mut r) = (a.clone(), b.clone());
+        let (mut old_s, mut s) = (ident::unit(), ident::zero());
+        let (mut old_t, mut t) = (ident::zero(), ident::unit());
+
+        while r != ident::zero() {
+            let q = t_div(&old_r, &r);
+
+            let tmp_r = old_r - q.clone() * r.clone();
+            old_r = r.clone();
+            r = tmp_r;
+
+            let tmp_s = old_s - q.clone() * s.clone();
+            old_s = s.clone();
+            s = tmp_s;
+
+            let tmp_t = old_t - q.clone() * t.clone();
+            old_t = t.clone();
+            t = tmp_t;
+        }
+
+        // With negative inputs the last non-zero remainder can come out
+        // negative: both inputs negative (`t_div(-x, -y) == t_div(x, y)`,
+        // so every remainder flips sign), and some mixed-sign inputs too,
+        // e.g. `(4, -6)` ends at `-2`. Negating all three keeps
+        // `a*old_s + b*old_t == old_r` and makes `old_r` the non-negative
+        // `gcd(a, b)`, as documented.
+        if old_r < ident::zero() {
+            old_r = ident::zero() - old_r;
+            old_s = ident::zero() - old_s;
+            old_t = ident::zero() - old_t;
+        }
+
+        (old_r, old_s, old_t)
+    }
+
+    /// The multiplicative inverse of `x` modulo `modulus`, or `None` if
+    /// `x` and `modulus` are not coprime (i.e. `x` is not a unit modulo
+    /// `modulus`).
+    fn inverse(x: &Self, modulus: &Self) -> Option<Self> {
+        let (g, a, _) = ModularInteger::extended_gcd(x, modulus);
+        if g != ident::unit() {
+            None
+        } else {
+            Some(e_mod(&a, modulus))
+        }
+    }
 }
 
 #[inline]
@@ -124,10 +232,255 @@ pub fn f_div_mod<T: Integer>(a: &T, b: &T) -> (T, T) {
     Integer::f_div_mod(a, b)
 }
 
-trait Real
+#[inline]
+pub fn e_div<T: Integer>(a: &T, b: &T) -> T {
+    Integer::e_div(a, b)
+}
+
+#[inline]
+pub fn e_mod<T: Integer>(a: &T, b: &T) -> T {
+    Integer::e_mod(a, b)
+}
+
+#[inline]
+pub fn e_div_mod<T: Integer>(a: &T, b: &T) -> (T, T) {
+    Integer::e_div_mod(a, b)
+}
+
+macro_rules! int_trait_impl {
+    ($($T:ident)*) => {$(
+        impl Integer for $T {
+            #[inline]
+            fn t_div(a: &$T, b: &$T) -> $T { *a / *b }
+
+            #[inline]
+            fn t_mod(a: &$T, b: &$T) -> $T { *a % *b }
+
+            #[inline]
+            fn f_div(a: &$T, b: &$T) -> $T {
+                let (q, r) = Integer::t_div_mod(a, b);
+                if r != 0 && (*a < 0) != (*b < 0) {
+                    q - 1
+                } else {
+                    q
+                }
+            }
+
+            #[inline]
+            fn f_mod(a: &$T, b: &$T) -> $T {
+                let r = Integer::t_mod(a, b);
+                if r != 0 && (*a < 0) != (*b < 0) {
+                    r + *b
+                } else {
+                    r
+                }
+            }
+
+            /// # Panics
+            ///
+            /// Panics if `a` or `b` is `$T::min_value()`, since its
+            /// absolute value overflows `$T`.
+            #[inline]
+            fn gcd(a: &$T, b: &$T) -> $T {
+                let mut a = a.abs();
+                let mut b = b.abs();
+                while b != 0 {
+                    let r = a % b;
+                    a = b;
+                    b = r;
+                }
+                a
+            }
+
+            #[inline]
+            fn lcm(a: &$T, b: &$T) -> $T {
+                if *a == 0 && *b == 0 {
+                    0
+                } else {
+                    *a / Integer::gcd(a, b) * *b
+                }
+            }
+        }
+    )*}
+}
+
+macro_rules! uint_trait_impl {
+    ($($T:ident)*) => {$(
+        impl Integer for $T {
+            #[inline]
+            fn t_div(a: &$T, b: &$T) -> $T { *a / *b }
+
+            #[inline]
+            fn t_mod(a: &$T, b: &$T) -> $T { *a % *b }
+
+            #[inline]
+            fn f_div(a: &$T, b: &$T) -> $T { *a / *b }
+
+            #[inline]
+            fn f_mod(a: &$T, b: &$T) -> $T { *a % *b }
+
+            #[inline]
+            fn gcd(a: &$T, b: &$T) -> $T {
+                let mut a = *a;
+                let mut b = *b;
+                while b != 0 {
+                    let r = a % b;
+                    a = b;
+                    b = r;
+                }
+                a
+            }
+
+            #[inline]
+            fn lcm(a: &$T, b: &$T) -> $T {
+                if *a == 0 && *b == 0 {
+                    0
+                } else {
+                    *a / Integer::gcd(a, b) * *b
+                }
+            }
+        }
+    )*}
+}
+
+int_trait_impl!(i8 i16 i32 i64 isize);
+uint_trait_impl!(u8 u16 u32 u64 usize);
+
+/// `i32` is a `ModularInteger` over its own machine-width range: there is
+/// no separate modulus, so `congruent` reduces to equality and the
+/// bounds are simply `i32::min_value()`/`i32::max_value()`. This exists
+/// primarily so `extended_gcd`/`inverse` have a concrete implementor to
+/// be tested against below.
+impl ModularInteger for i32 {
+    #[inline]
+    fn min_value() -> i32 { i32::min_value() }
+
+    #[inline]
+    fn max_value() -> i32 { i32::max_value() }
+
+    #[inline]
+    fn congruent(x: &i32, y: &i32) -> bool { x == y }
+}
+
+pub trait Real
     : PartialOrd
     + Field {
 }
 
 impl Real for f32  {}
 impl Real for f64  {}
+
+#[cfg(test)]
+mod tests {
+    use super::{e_div, e_mod, f_div, f_mod, t_div, t_mod, Integer, ModularInteger};
+
+    #[test]
+    fn test_f_div_f_mod_sign_combinations() {
+        assert_eq!(f_div(&8, &3), 2);
+        assert_eq!(f_mod(&8, &3), 2);
+        assert_eq!(f_div(&8, &-3), -3);
+        assert_eq!(f_mod(&8, &-3), -1);
+        assert_eq!(f_div(&-8, &3), -3);
+        assert_eq!(f_mod(&-8, &3), 1);
+        assert_eq!(f_div(&-8, &-3), 2);
+        assert_eq!(f_mod(&-8, &-3), -2);
+    }
+
+    #[test]
+    fn test_e_div_e_mod_sign_combinations() {
+        assert_eq!(e_div(&8, &3), 2);
+        assert_eq!(e_mod(&8, &3), 2);
+        assert_eq!(e_div(&8, &-3), -2);
+        assert_eq!(e_mod(&8, &-3), 2);
+        assert_eq!(e_div(&-8, &3), -3);
+        assert_eq!(e_mod(&-8, &3), 1);
+        assert_eq!(e_div(&-8, &-3), 3);
+        assert_eq!(e_mod(&-8, &-3), 1);
+
+        // The defining postcondition: 0 <= e_mod(a, b) < |b|, and
+        // a == b * e_div(a, b) + e_mod(a, b), for every sign combination.
+        for &(a, b) in &[(8, 3), (8, -3), (-8, 3), (-8, -3)] {
+            let r = e_mod(&a, &b);
+            assert!(r >= 0 && r < b.abs());
+            assert_eq!(a, b * e_div(&a, &b) + r);
+        }
+    }
+
+    #[test]
+    fn test_t_div_t_mod() {
+        assert_eq!(t_div(&-8, &3), -2);
+        assert_eq!(t_mod(&-8, &3), -2);
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(Integer::gcd(&12i32, &18i32), 6);
+        assert_eq!(Integer::gcd(&-12i32, &18i32), 6);
+        assert_eq!(Integer::lcm(&4i32, &6i32), 12);
+
+        assert_eq!(Integer::gcd(&12u32, &18u32), 6);
+        assert_eq!(Integer::lcm(&4u32, &6u32), 12);
+    }
+
+    #[test]
+    fn test_lcm_zero_zero() {
+        // gcd(0, 0) == 0, so the naive `a / gcd(a, b) * b` divides by
+        // zero; lcm(0, 0) must be special-cased to 0 instead.
+        assert_eq!(Integer::lcm(&0i32, &0i32), 0);
+        assert_eq!(Integer::lcm(&0u32, &0u32), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gcd_min_value_overflows() {
+        // `i32::min_value().abs()` has no representable result, so `gcd`
+        // panics on this input; see the doc comment on the macro-generated
+        // `gcd` impl.
+        Integer::gcd(&i32::min_value(), &1i32);
+    }
+
+    #[test]
+    fn test_even_odd_divides() {
+        assert!(Integer::is_even(&4i32));
+        assert!(!Integer::is_odd(&4i32));
+        assert!(Integer::is_odd(&5i32));
+        assert!(!Integer::is_even(&5i32));
+
+        assert!(Integer::divides(&3i32, &9i32));
+        assert!(!Integer::divides(&4i32, &9i32));
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let (g, x, y) = ModularInteger::extended_gcd(&240i32, &46i32);
+        assert_eq!((g, x, y), (2, -9, 47));
+        assert_eq!(240 * x + 46 * y, g);
+    }
+
+    #[test]
+    fn test_extended_gcd_negative_negative() {
+        // Both inputs negative must not flip the sign of the returned gcd.
+        let (g, x, y) = ModularInteger::extended_gcd(&-4i32, &-6i32);
+        assert_eq!(g, 2);
+        assert_eq!(-4 * x + -6 * y, g);
+
+        let (g, _, _) = ModularInteger::extended_gcd(&-3i32, &-7i32);
+        assert_eq!(g, 1);
+    }
+
+    #[test]
+    fn test_inverse() {
+        // 3 * 5 = 15 = 2*7 + 1, so 3's inverse mod 7 is 5.
+        assert_eq!(ModularInteger::inverse(&3i32, &7i32), Some(5));
+
+        // 4 and 8 share a factor of 4, so 4 has no inverse mod 8.
+        assert_eq!(ModularInteger::inverse(&4i32, &8i32), None);
+
+        // 9's inverse mod 26: 9*3 = 27 = 26 + 1.
+        assert_eq!(ModularInteger::inverse(&9i32, &26i32), Some(3));
+
+        // Negative modulus/value: gcd(-3, -7) == 1, so this is genuinely
+        // invertible and must not be corrupted by a sign-flipped gcd.
+        assert_eq!(ModularInteger::inverse(&-3i32, &-7i32), Some(2));
+    }
+}
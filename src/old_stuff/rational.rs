@@ -0,0 +1,228 @@
+// Copyright 2013-2014 The Num-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generic rational numbers, parameterized over any `Integer`
+//! implementation (including `BigInt`, for unbounded exact fractions).
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use algebra::{CommutativeRing, Field};
+use ident;
+use old_stuff::num::{Integer, Real};
+
+/// A rational number `num / den`, always kept in canonical reduced form:
+/// `gcd(num, den) == 1` and `den` is positive, with any overall sign
+/// folded into `num`.
+#[derive(Clone, Debug)]
+pub struct Rational<T: Integer> {
+    num: T,
+    den: T,
+}
+
+impl<T: Integer> Rational<T> {
+    /// Creates a new rational number `num / den`, reducing it to
+    /// canonical form.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `den` is zero.
+    pub fn new(num: T, den: T) -> Rational<T> {
+        assert!(den != ident::zero(), "Rational::new: zero denominator");
+        Rational { num: num, den: den }.reduce()
+    }
+
+    pub fn numer(&self) -> &T {
+        &self.num
+    }
+
+    pub fn denom(&self) -> &T {
+        &self.den
+    }
+
+    /// Divides `num` and `den` by their GCD, and moves the sign of `den`
+    /// onto `num` so that `den` is always positive.
+    fn reduce(self) -> Rational<T> {
+        let g = Integer::gcd(&self.num, &self.den);
+        let mut num = Integer::t_div(&self.num, &g);
+        let mut den = Integer::t_div(&self.den, &g);
+        if den < ident::zero() {
+            num = ident::zero() - num;
+            den = ident::zero() - den;
+        }
+        Rational { num: num, den: den }
+    }
+
+    /// The multiplicative inverse of this rational number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the numerator is zero.
+    pub fn recip(&self) -> Rational<T> {
+        assert!(self.num != ident::zero(), "cannot invert zero");
+        Rational::new(self.den.clone(), self.num.clone())
+    }
+}
+
+impl<T: Integer> PartialEq for Rational<T> {
+    fn eq(&self, other: &Rational<T>) -> bool {
+        self.num == other.num && self.den == other.den
+    }
+}
+
+impl<T: Integer> Eq for Rational<T> {}
+
+impl<T: Integer> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Rational<T>) -> Option<Ordering> {
+        // Both denominators are positive in canonical form, so the cross
+        // products preserve order without any extra sign handling.
+        (self.num.clone() * other.den.clone()).partial_cmp(&(other.num.clone() * self.den.clone()))
+    }
+}
+
+impl<T: Integer> CommutativeRing for Rational<T> {}
+
+impl<T: Integer> Real for Rational<T> {}
+
+impl<'a, 'b, T: Integer> Add<&'b Rational<T>> for &'a Rational<T> {
+    type Output = Rational<T>;
+
+    fn add(self, other: &'b Rational<T>) -> Rational<T> {
+        let num = self.num.clone() * other.den.clone() + other.num.clone() * self.den.clone();
+        let den = self.den.clone() * other.den.clone();
+        Rational::new(num, den)
+    }
+}
+
+impl<'a, 'b, T: Integer> Sub<&'b Rational<T>> for &'a Rational<T> {
+    type Output = Rational<T>;
+
+    fn sub(self, other: &'b Rational<T>) -> Rational<T> {
+        let num = self.num.clone() * other.den.clone() - other.num.clone() * self.den.clone();
+        let den = self.den.clone() * other.den.clone();
+        Rational::new(num, den)
+    }
+}
+
+impl<'a, 'b, T: Integer> Mul<&'b Rational<T>> for &'a Rational<T> {
+    type Output = Rational<T>;
+
+    fn mul(self, other: &'b Rational<T>) -> Rational<T> {
+        Rational::new(self.num.clone() * other.num.clone(), self.den.clone() * other.den.clone())
+    }
+}
+
+impl<'a, 'b, T: Integer> Div<&'b Rational<T>> for &'a Rational<T> {
+    type Output = Rational<T>;
+
+    fn div(self, other: &'b Rational<T>) -> Rational<T> {
+        self * &other.recip()
+    }
+}
+
+impl<T: Integer> Neg for Rational<T> {
+    type Output = Rational<T>;
+
+    fn neg(self) -> Rational<T> {
+        Rational { num: ident::zero() - self.num, den: self.den }
+    }
+}
+
+impl<T: Integer> Add for Rational<T> {
+    type Output = Rational<T>;
+    fn add(self, other: Rational<T>) -> Rational<T> {
+        &self + &other
+    }
+}
+
+impl<T: Integer> Sub for Rational<T> {
+    type Output = Rational<T>;
+    fn sub(self, other: Rational<T>) -> Rational<T> {
+        &self - &other
+    }
+}
+
+impl<T: Integer> Mul for Rational<T> {
+    type Output = Rational<T>;
+    fn mul(self, other: Rational<T>) -> Rational<T> {
+        &self * &other
+    }
+}
+
+impl<T: Integer> Div for Rational<T> {
+    type Output = Rational<T>;
+    fn div(self, other: Rational<T>) -> Rational<T> {
+        &self / &other
+    }
+}
+
+impl<T: Integer> Field for Rational<T> {
+    fn recip(&self) -> Rational<T> {
+        Rational::recip(self)
+    }
+}
+
+impl<T: Integer + fmt::Display> fmt::Display for Rational<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.num, self.den)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rational;
+
+    #[test]
+    fn test_reduces_to_canonical_form() {
+        let r = Rational::new(4, 8);
+        assert_eq!(*r.numer(), 1);
+        assert_eq!(*r.denom(), 2);
+
+        let r = Rational::new(2, -4);
+        assert_eq!(*r.numer(), -1);
+        assert_eq!(*r.denom(), 2);
+    }
+
+    #[test]
+    fn test_add_sub_mul_div() {
+        let a = Rational::new(1, 2);
+        let b = Rational::new(1, 3);
+        assert_eq!(a.clone() + b.clone(), Rational::new(5, 6));
+        assert_eq!(a.clone() - b.clone(), Rational::new(1, 6));
+        assert_eq!(a.clone() * b.clone(), Rational::new(1, 6));
+        assert_eq!(a.clone() / b.clone(), Rational::new(3, 2));
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(Rational::new(1, 3) < Rational::new(1, 2));
+        assert!(Rational::new(-1, 2) < Rational::new(1, 3));
+    }
+
+    #[test]
+    fn test_rational_of_bigint() {
+        // The whole point of making `Rational` generic over any `Integer`
+        // is that it composes with `BigInt` for unbounded exact
+        // fractions; exercise that composition directly rather than just
+        // asserting it in prose.
+        use old_stuff::bigint::BigInt;
+
+        let a = Rational::new(BigInt::from_i64(1), BigInt::from_i64(2));
+        let b = Rational::new(BigInt::from_i64(1), BigInt::from_i64(3));
+        let sum = a + b;
+        assert_eq!(*sum.numer(), BigInt::from_i64(5));
+        assert_eq!(*sum.denom(), BigInt::from_i64(6));
+    }
+}